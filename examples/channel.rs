@@ -0,0 +1,36 @@
+use mio::channel::{self, Receiver};
+use mio::{Events, Interest, Poll, Token};
+
+fn main() -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+    // 消息通道事件的TOKEN
+    const CHANNEL_TOKEN: Token = Token(1);
+
+    // 创建epoll实例
+    let mut poll = Poll::new()?;
+    // 创建存储就绪事件的数组
+    let mut events = Events::with_capacity(2);
+
+    // 创建消息通道,Receiver是一个事件源
+    let (tx, mut rx): (_, Receiver<&'static str>) = channel::channel();
+    poll.registry()
+        .register(&mut rx, CHANNEL_TOKEN, Interest::READABLE)?;
+
+    // 模拟在其它线程发送消息到事件循环
+    let handle = std::thread::spawn(move || {
+        tx.send("hello").expect("unable to send");
+    });
+
+    // 轮询就绪事件,收到消息通道的可读事件后排空消息
+    poll.poll(&mut events, None)?;
+
+    for event in events.iter() {
+        if event.token() == CHANNEL_TOKEN {
+            while let Ok(msg) = rx.try_recv() {
+                println!("received: {}", msg);
+            }
+        }
+    }
+
+    handle.join().unwrap();
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use mio::{Events, Interest, Poll, Timer, Token};
+
+fn main() -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+    // 定时器事件的TOKEN
+    const TIMER_TOKEN: Token = Token(1);
+
+    // 创建epoll实例
+    let mut poll = Poll::new()?;
+    // 创建存储就绪事件的数组
+    let mut events = Events::with_capacity(2);
+
+    // 创建时间轮定时器并注册到poll中
+    let mut timer = Timer::<&'static str>::new();
+    poll.registry()
+        .register(&mut timer, TIMER_TOKEN, Interest::READABLE)?;
+
+    // 设置两个定时器,分别在100ms和300ms后触发
+    timer.set_timeout(Duration::from_millis(100), "first");
+    timer.set_timeout(Duration::from_millis(300), "second");
+
+    // 事件循环:把poll_timeout()的结果作为poll的超时时长,每次poll返回后都收割到期的定时器
+    while timer.poll_timeout().is_some() {
+        poll.poll(&mut events, timer.poll_timeout())?;
+
+        for payload in timer.expired() {
+            println!("timeout fired: {}", payload);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+use std::io;
+use std::sync::mpsc::{self, RecvError, SendError, TryRecvError};
+use std::sync::{Arc, Mutex};
+
+use crate::{event, Interest, Registry, Token, Waker};
+
+/// 创建一个无界的跨线程消息通道,`Receiver`可注册到[`Poll`]中。<br>
+/// Create an unbounded cross-thread channel whose `Receiver` can be
+/// registered with a [`Poll`].
+///
+/// [`Poll`]: crate::Poll
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    let state = Arc::new(WakeState::new());
+    (
+        Sender {
+            tx,
+            state: state.clone(),
+        },
+        Receiver { rx, state },
+    )
+}
+
+/// 创建一个有界的跨线程消息通道,`Receiver`可注册到[`Poll`]中。<br>
+/// Create a bounded cross-thread channel whose `Receiver` can be registered
+/// with a [`Poll`].
+///
+/// [`Poll`]: crate::Poll
+pub fn sync_channel<T>(bound: usize) -> (SyncSender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::sync_channel(bound);
+    let state = Arc::new(WakeState::new());
+    (
+        SyncSender {
+            tx,
+            state: state.clone(),
+        },
+        Receiver { rx, state },
+    )
+}
+
+/// 在`Sender`/`SyncSender`/`Receiver`之间共享的状态。<br>
+/// State shared between the `Sender`/`SyncSender` and `Receiver` halves.
+///
+/// 折叠"N次send只产生足够唤醒Poll一次的wake调用"这件事,本身已经由
+/// [`Waker`]正确地处理了(每个Waker拥有专属的eventfd,下一次`poll`之前的多次
+/// 唤醒只会累加它的计数器,而不是各自排队一个通知),所以这里不需要再维护
+/// 一个独立的、与实际收发动作脱节的计数器——此前正是这种脱节的计数器在多个
+/// 发送方和接收方并发时出现了下溢、永久丢失唤醒的问题。<br>
+/// Collapsing "N sends should only produce as many wakes as `Poll` actually
+/// needs" is already handled correctly by [`Waker`] itself (each `Waker`
+/// owns a dedicated eventfd, and repeated wakes before the next `poll` only
+/// add to its counter instead of queueing one notification each). There is
+/// no need to duplicate that bookkeeping here with a
+/// separate counter that isn't actually tied to the real send/receive
+/// actions — that kind of detached counter is exactly what previously
+/// underflowed and permanently lost wakeups under concurrent senders and
+/// receivers.
+struct WakeState {
+    /// 注册之后才存在,用于在Receiver所在的Poll上产生可读事件
+    waker: Mutex<Option<Waker>>,
+}
+
+impl WakeState {
+    fn new() -> WakeState {
+        WakeState {
+            waker: Mutex::new(None),
+        }
+    }
+
+    /// 一条消息已经发送,如果`Receiver`已注册就唤醒一次;[`Waker`]自身负责把
+    /// 多次唤醒折叠成最少的实际通知。<br>
+    /// A message has been sent; wake once if the `Receiver` is registered.
+    /// `Waker` itself is responsible for collapsing repeated wakes into the
+    /// minimum number of real notifications.
+    fn notify_sent(&self) {
+        if let Some(waker) = self.waker.lock().unwrap().as_ref() {
+            let _ = waker.wake();
+        }
+    }
+}
+
+/// [`channel`]返回的发送端。<br>
+/// The sending half returned by [`channel`].
+pub struct Sender<T> {
+    tx: mpsc::Sender<T>,
+    state: Arc<WakeState>,
+}
+
+impl<T> Sender<T> {
+    /// 发送一条消息,如果`Receiver`已注册,会产生一次可读事件。<br>
+    /// Send a message. If the `Receiver` is registered this produces a
+    /// readable event.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.tx.send(value)?;
+        self.state.notify_sent();
+        Ok(())
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        Sender {
+            tx: self.tx.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// [`sync_channel`]返回的发送端。<br>
+/// The sending half returned by [`sync_channel`].
+pub struct SyncSender<T> {
+    tx: mpsc::SyncSender<T>,
+    state: Arc<WakeState>,
+}
+
+impl<T> SyncSender<T> {
+    /// 发送一条消息,如果通道已满会阻塞,如果`Receiver`已注册,会产生一次可读事件。<br>
+    /// Send a message, blocking if the channel is full. If the `Receiver` is
+    /// registered this produces a readable event.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.tx.send(value)?;
+        self.state.notify_sent();
+        Ok(())
+    }
+}
+
+impl<T> Clone for SyncSender<T> {
+    fn clone(&self) -> SyncSender<T> {
+        SyncSender {
+            tx: self.tx.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SyncSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncSender").finish_non_exhaustive()
+    }
+}
+
+/// 由[`channel`]或[`sync_channel`]返回的接收端,实现了[`event::Source`]。<br>
+/// The receiving half returned by [`channel`] or [`sync_channel`]. Implements
+/// [`event::Source`] so it can be registered with a [`Poll`].
+///
+/// 注册后每一次成功的`send`都会在对应的`Token`上产生一次可读事件,事件循环应当
+/// 通过[`try_recv`]循环取出消息直到遇到[`TryRecvError::Empty`]。<br>
+/// Once registered every successful `send` produces a readable event on the
+/// registered `Token`. The event loop should drain messages with
+/// [`try_recv`] until it returns [`TryRecvError::Empty`].
+///
+/// # 注意, Notes
+///
+/// 和[`IoSource`]一样,应当先注册`Receiver`再发送消息:只有注册之后的`send`
+/// 才能观察到内部的[`Waker`]并据此产生唤醒,在注册之前发送的消息不会补发
+/// 唤醒事件。<br>
+/// As with [`IoSource`], register the `Receiver` before sending any
+/// messages: only a `send` that happens after registration can observe the
+/// internal [`Waker`] and wake the poller; messages sent before registration
+/// do not retroactively produce a wakeup.
+///
+/// [`Poll`]: crate::Poll
+/// [`IoSource`]: crate::IoSource
+/// [`try_recv`]: Receiver::try_recv
+pub struct Receiver<T> {
+    rx: mpsc::Receiver<T>,
+    state: Arc<WakeState>,
+}
+
+impl<T> Receiver<T> {
+    /// 非阻塞地尝试取出一条消息。<br>
+    /// Attempt to take a message without blocking.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.rx.try_recv()
+    }
+
+    /// 阻塞直到有一条消息或者所有发送端都被丢弃。<br>
+    /// Block until a message arrives or every sending half is dropped.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.rx.recv()
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+impl<T> event::Source for Receiver<T> {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        _interests: Interest,
+    ) -> io::Result<()> {
+        let waker = Waker::new(registry, token)?;
+        *self.state.waker.lock().unwrap() = Some(waker);
+        Ok(())
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.register(registry, token, interests)
+    }
+
+    fn deregister(&mut self, _registry: &Registry) -> io::Result<()> {
+        *self.state.waker.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Events, Poll};
+
+    #[test]
+    fn send_then_recv_returns_the_value() {
+        let (tx, rx) = channel();
+        tx.send(42).unwrap();
+        assert_eq!(rx.try_recv(), Ok(42));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn sync_channel_send_then_recv_returns_the_value() {
+        let (tx, rx) = sync_channel(1);
+        tx.send("a").unwrap();
+        assert_eq!(rx.try_recv(), Ok("a"));
+    }
+
+    /// 回归测试:`Receiver`与另一个携带不同Token的`Waker`共享同一个`Poll`时,
+    /// 两者各自的事件都必须被正确投递,互不干扰。这正是chunk0-3曾经破坏过的
+    /// 场景——如果多个Waker共用并在用户态多路复用同一个eventfd,却没有真正
+    /// 把单次可读通知翻译回正确的per-token事件,这里通道的可读事件就会丢失
+    /// 或者被错误地报告在另一个token上。<br>
+    /// Regression test: when a `Receiver` shares a `Poll` with another
+    /// `Waker` using a distinct token, both must deliver their own events
+    /// without interfering with each other. This is exactly the scenario
+    /// chunk0-3 used to break — if several wakers shared and multiplexed one
+    /// eventfd in userspace without actually translating a single readable
+    /// notification back into correct per-token events, the channel's
+    /// readable event would be lost or misreported under the other token.
+    #[test]
+    fn receiver_and_a_second_waker_on_the_same_poll_both_deliver() {
+        const CHANNEL_TOKEN: Token = Token(0);
+        const OTHER_TOKEN: Token = Token(1);
+
+        let mut poll = Poll::new().unwrap();
+        let mut events = Events::with_capacity(4);
+
+        let (tx, mut rx) = channel();
+        poll.registry()
+            .register(&mut rx, CHANNEL_TOKEN, Interest::READABLE)
+            .unwrap();
+
+        let other_waker = Waker::new(poll.registry(), OTHER_TOKEN).unwrap();
+        other_waker.wake().unwrap();
+        tx.send("hello").unwrap();
+
+        poll.poll(&mut events, None).unwrap();
+
+        assert!(events.iter().any(|event| event.token() == CHANNEL_TOKEN));
+        assert!(events.iter().any(|event| event.token() == OTHER_TOKEN));
+        assert_eq!(rx.try_recv(), Ok("hello"));
+    }
+}
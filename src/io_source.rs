@@ -1,4 +1,4 @@
-use std::ops::{Deref, DerefMut};
+use std::ops::{ControlFlow, Deref, DerefMut};
 #[cfg(unix)]
 use std::os::unix::io::AsRawFd;
 #[cfg(target_os = "wasi")]
@@ -7,6 +7,7 @@ use std::os::wasi::io::AsRawFd;
 use std::os::windows::io::AsRawSocket;
 #[cfg(debug_assertions)]
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task;
 use std::{fmt, io};
 
 use crate::sys::IoSourceState;
@@ -34,8 +35,20 @@ use crate::{event, Interest, Registry, Token};
 /// as `read`, `write`, etc.) must go through the [`do_io`] method to ensure the
 /// internal state is updated accordingly.
 ///
+/// 如果使用[`poll_read_ready`]/[`poll_write_ready`]/[`readable`]/[`writable`]
+/// 等待异步就绪,还必须为每个观察到的事件调用[`notify`],否则等待中的任务永远
+/// 不会被唤醒.<br>
+/// If waiting for async readiness via [`poll_read_ready`]/[`poll_write_ready`]/
+/// [`readable`]/[`writable`], every observed event must also be forwarded to
+/// [`notify`], or waiting tasks will never be woken.
+///
 /// [`Poll`]: crate::Poll
 /// [`do_io`]: IoSource::do_io
+/// [`poll_read_ready`]: IoSource::poll_read_ready
+/// [`poll_write_ready`]: IoSource::poll_write_ready
+/// [`readable`]: IoSource::readable
+/// [`writable`]: IoSource::writable
+/// [`notify`]: IoSource::notify
 ///
 /// # 例子, Examples
 ///
@@ -106,6 +119,55 @@ impl<T> IoSource<T> {
         self.state.do_io(f, &self.inner)
     }
 
+    /// 重复执行一个IO操作直到遇到`WouldBlock`,将每次成功的结果收集起来返回,
+    /// 适用于边缘触发(edge-triggered)场景下需要一次性把套接字排空的情形.<br>
+    /// Repeatedly executes an I/O operation until it returns [`WouldBlock`],
+    /// accumulating the results. Intended for edge-triggered workloads, where
+    /// correctly draining a ready socket requires looping until `WouldBlock`
+    /// instead of stopping after a single successful call.
+    ///
+    /// # 注意, Notes
+    ///
+    /// `f`每次返回`ControlFlow::Continue(r)`就继续循环并收集`r`,返回
+    /// `ControlFlow::Break(r)`则收集`r`后提前停止.真正的`WouldBlock`错误
+    /// 被当作正常的循环终止条件处理,`Interrupted`会被直接重试,其它错误会
+    /// 立即向上传播.不论循环执行了多少次,[`IoSourceState`]只会在整个
+    /// `do_io_until_block`结束时更新一次,这与[`do_io`]在每次调用时都更新
+    /// 的行为不同.<br>
+    /// Each call to `f` returning `ControlFlow::Continue(r)` keeps looping
+    /// and collects `r`; returning `ControlFlow::Break(r)` collects `r` and
+    /// stops early. A genuine [`WouldBlock`] is treated as the normal loop
+    /// terminator (the source stays armed), `Interrupted` is retried in
+    /// place, and any other error is surfaced immediately. Regardless of how
+    /// many iterations run, the internal I/O source state is updated exactly
+    /// once, at the end, unlike [`do_io`] which updates it on every call.
+    ///
+    /// [`WouldBlock`]: io::ErrorKind::WouldBlock
+    /// [`do_io`]: IoSource::do_io
+    pub fn do_io_until_block<F, R>(&self, mut f: F) -> io::Result<Vec<R>>
+    where
+        F: FnMut(&T) -> io::Result<ControlFlow<R, R>>,
+    {
+        let mut results = Vec::new();
+        let outcome = self.do_io(|io| loop {
+            match f(io) {
+                Ok(ControlFlow::Continue(r)) => results.push(r),
+                Ok(ControlFlow::Break(r)) => {
+                    results.push(r);
+                    return Ok(());
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        });
+
+        match outcome {
+            Ok(()) => Ok(results),
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(results),
+            Err(err) => Err(err),
+        }
+    }
+
     /// 剥出内部事件源.<br>
     /// Returns the I/O source, dropping the state.
     ///
@@ -121,6 +183,137 @@ impl<T> IoSource<T> {
     pub fn into_inner(self) -> T {
         self.inner
     }
+
+    /// 异步地等待可读,尚未就绪时存入读方向的任务唤醒器.<br>
+    /// Asynchronously wait for the source to become readable. While it is not
+    /// ready, each poll stores the task's waker for the read direction; no
+    /// interest is registered with the selector here.
+    ///
+    /// 与[`do_io`]一样,要求`IoSource`已经通过[`Registry::register`]注册到
+    /// 一个`Poll`上.<br>
+    /// As with [`do_io`], this requires the `IoSource` to already be
+    /// registered with a `Poll` via [`Registry::register`].
+    ///
+    /// [`do_io`]: IoSource::do_io
+    pub fn poll_read_ready<'a>(
+        &'a self,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<io::Result<ReadyGuard<'a, T>>> {
+        self.state.poll_read_ready(cx).map_ok(|()| ReadyGuard {
+            io: self,
+            direction: Direction::Read,
+        })
+    }
+
+    /// 异步地等待可写,尚未就绪时存入写方向的任务唤醒器.<br>
+    /// Asynchronously wait for the source to become writable. While it is not
+    /// ready, each poll stores the task's waker for the write direction; no
+    /// interest is registered with the selector here.
+    ///
+    /// [`do_io`]: IoSource::do_io
+    pub fn poll_write_ready<'a>(
+        &'a self,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<io::Result<ReadyGuard<'a, T>>> {
+        self.state.poll_write_ready(cx).map_ok(|()| ReadyGuard {
+            io: self,
+            direction: Direction::Write,
+        })
+    }
+
+    /// [`poll_read_ready`]的`async fn`便捷封装.<br>
+    /// Convenience `async fn` wrapper around [`poll_read_ready`].
+    ///
+    /// [`poll_read_ready`]: IoSource::poll_read_ready
+    pub async fn readable(&self) -> io::Result<ReadyGuard<'_, T>> {
+        std::future::poll_fn(|cx| self.poll_read_ready(cx)).await
+    }
+
+    /// [`poll_write_ready`]的`async fn`便捷封装.<br>
+    /// Convenience `async fn` wrapper around [`poll_write_ready`].
+    ///
+    /// [`poll_write_ready`]: IoSource::poll_write_ready
+    pub async fn writable(&self) -> io::Result<ReadyGuard<'_, T>> {
+        std::future::poll_fn(|cx| self.poll_write_ready(cx)).await
+    }
+
+    /// 把事件循环观察到的一个事件转发给这个源,如果其token匹配,就唤醒正在
+    /// 等待对应方向就绪的任务.<br>
+    /// Feed an event observed by the event loop into this source. If its
+    /// token matches the one this source is currently registered under, any
+    /// task waiting on [`poll_read_ready`]/[`poll_write_ready`] for the
+    /// direction(s) the event reports is woken.
+    ///
+    /// # 注意, Notes
+    ///
+    /// 就像事件循环必须调用[`Timer::expired`]来收割到期的定时器一样,
+    /// 使用[`poll_read_ready`]/[`poll_write_ready`]/[`readable`]/[`writable`]
+    /// 的代码必须为每一个可能属于这个源的事件调用`notify`——`Poll::poll`本身
+    /// 不会替你把事件路由回某个具体的`IoSource`.<br>
+    /// Just as an event loop must call [`Timer::expired`] to collect expired
+    /// timeouts, code using [`poll_read_ready`]/[`poll_write_ready`]/
+    /// [`readable`]/[`writable`] must call `notify` with every event that
+    /// could belong to this source — `Poll::poll` itself does not route
+    /// events back to a specific `IoSource` for you.
+    ///
+    /// [`poll_read_ready`]: IoSource::poll_read_ready
+    /// [`poll_write_ready`]: IoSource::poll_write_ready
+    /// [`readable`]: IoSource::readable
+    /// [`writable`]: IoSource::writable
+    /// [`Timer::expired`]: crate::Timer::expired
+    pub fn notify(&self, event: &event::Event) {
+        self.state.notify(event);
+    }
+}
+
+/// 就绪方向,区分任务是在等待可读还是可写.<br>
+/// Which readiness direction a [`ReadyGuard`] was obtained for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Read,
+    Write,
+}
+
+/// 由[`IoSource::poll_read_ready`]/[`IoSource::poll_write_ready`]返回的就绪凭证.<br>
+/// A readiness token returned by [`IoSource::poll_read_ready`] /
+/// [`IoSource::poll_write_ready`].
+///
+/// 如果底层操作返回`WouldBlock`,调用[`ReadyGuard::try_io`]会清除对应方向缓存的
+/// 就绪标记,这样下一次`poll_read_ready`/`poll_write_ready`会重新等待一次真正
+/// 的就绪通知,而不是立即又返回`Ready`造成busy-loop.这与[`do_io`]的状态机约定
+/// 是一致的.<br>
+/// If the underlying operation returns `WouldBlock`, calling
+/// [`ReadyGuard::try_io`] clears the cached readiness bit for that
+/// direction, so the next `poll_read_ready`/`poll_write_ready` waits for a
+/// fresh readiness notification instead of immediately returning `Ready`
+/// again. This matches the state-machine contract of [`do_io`].
+///
+/// [`do_io`]: IoSource::do_io
+#[derive(Debug)]
+pub struct ReadyGuard<'a, T> {
+    io: &'a IoSource<T>,
+    direction: Direction,
+}
+
+impl<'a, T> ReadyGuard<'a, T> {
+    /// 使用这个凭证执行一次IO操作,遇到`WouldBlock`时清除缓存的就绪标记.<br>
+    /// Perform an I/O operation using this readiness token, clearing the
+    /// cached readiness bit if it returns `WouldBlock`.
+    pub fn try_io<F, R>(&self, f: F) -> io::Result<R>
+    where
+        F: FnOnce(&T) -> io::Result<R>,
+    {
+        match f(&self.io.inner) {
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                match self.direction {
+                    Direction::Read => self.io.state.clear_read_ready(),
+                    Direction::Write => self.io.state.clear_write_ready(),
+                }
+                Err(err)
+            }
+            other => other,
+        }
+    }
 }
 
 /// 使用此方法时要注意,所有可能阻塞的IO操作必须先执行do_io
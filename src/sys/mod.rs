@@ -0,0 +1,4 @@
+#[cfg(unix)]
+mod unix;
+#[cfg(unix)]
+pub(crate) use unix::{IoSourceState, Waker};
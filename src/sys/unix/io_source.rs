@@ -0,0 +1,174 @@
+use std::io;
+use std::os::unix::io::RawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::task;
+
+use crate::event::Event;
+use crate::{Interest, Registry, Token};
+
+/// 单个方向(可读或可写)的异步就绪状态:一个"是否已就绪"的标志,加上最近一次
+/// `poll_*_ready`存入的任务唤醒器.<br>
+/// Async readiness state for a single direction (read or write): a flag
+/// recording whether the direction is currently believed ready, plus the
+/// task waker stored by the most recent `poll_*_ready` call.
+#[derive(Debug, Default)]
+struct Readiness {
+    ready: AtomicBool,
+    waker: Mutex<Option<task::Waker>>,
+}
+
+impl Readiness {
+    fn poll(&self, cx: &mut task::Context<'_>) -> task::Poll<()> {
+        // 就绪标志在这里只读取不清除,只有`clear`(例如`ReadyGuard::try_io`
+        // 遇到`WouldBlock`时)才会清除它.
+        // The flag is only read here, not cleared; only `clear` (for example
+        // from `ReadyGuard::try_io` on `WouldBlock`) resets it.
+        if self.ready.load(Ordering::Acquire) {
+            return task::Poll::Ready(());
+        }
+
+        *self.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        // 重新检查一次:在上面的检查和这里存入waker之间送达的就绪通知不应该
+        // 被错过.
+        // Check again: a readiness notification delivered between the check
+        // above and storing the waker must not be missed.
+        if self.ready.load(Ordering::Acquire) {
+            return task::Poll::Ready(());
+        }
+
+        task::Poll::Pending
+    }
+
+    fn clear(&self) {
+        self.ready.store(false, Ordering::Release);
+    }
+
+    fn set(&self) {
+        self.ready.store(true, Ordering::Release);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Unix上[`IoSource`]的内部状态.<br>
+/// Unix-specific internal state backing [`IoSource`].
+///
+/// 普通IO操作([`do_io`])在Unix上是直接转发,真正的就绪语义已经由epoll/kqueue
+/// 在OS层面保证,不需要额外记录.但异步就绪API
+/// ([`poll_read_ready`]/[`poll_write_ready`])需要记录每个方向各自的就绪标志
+/// 和任务唤醒器,这些标志由[`IoSource::notify`]在事件循环观察到匹配token的
+/// 事件时写入——就像事件循环必须手动调用[`Timer::expired`]收割到期定时器
+/// 一样,它也必须把观察到的事件转发给`notify`,才能让等待中的任务被唤醒.<br>
+/// Plain I/O ([`do_io`]) is a direct passthrough on Unix — readiness
+/// semantics are already guaranteed by epoll/kqueue at the OS level, so
+/// nothing extra needs tracking there. But the async readiness API
+/// ([`poll_read_ready`]/[`poll_write_ready`]) needs a per-direction
+/// readiness flag and task waker, populated by [`IoSource::notify`]
+/// whenever the event loop observes an event for this source's token —
+/// much like an event loop must manually call [`Timer::expired`] to collect
+/// expired timeouts, it must also forward observed events to `notify` for
+/// waiting tasks to be woken.
+///
+/// [`IoSource`]: crate::IoSource
+/// [`do_io`]: crate::IoSource::do_io
+/// [`poll_read_ready`]: crate::IoSource::poll_read_ready
+/// [`poll_write_ready`]: crate::IoSource::poll_write_ready
+/// [`IoSource::notify`]: crate::IoSource::notify
+/// [`Timer::expired`]: crate::Timer::expired
+#[derive(Debug, Default)]
+pub(crate) struct IoSourceState {
+    /// 最近一次register/reregister使用的token,用于在`notify`中判断一个事件
+    /// 是否属于这个源.<br>
+    /// The token passed to the most recent register/reregister call, used by
+    /// `notify` to tell whether an event belongs to this source.
+    token: Mutex<Option<Token>>,
+    read: Readiness,
+    write: Readiness,
+}
+
+impl IoSourceState {
+    pub(crate) fn new() -> IoSourceState {
+        IoSourceState::default()
+    }
+
+    pub(crate) fn do_io<T, F, R>(&self, f: F, io: &T) -> io::Result<R>
+    where
+        F: FnOnce(&T) -> io::Result<R>,
+    {
+        f(io)
+    }
+
+    pub(crate) fn register(
+        &self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+        fd: RawFd,
+    ) -> io::Result<()> {
+        registry.selector().register(fd, token, interests)?;
+        *self.token.lock().unwrap() = Some(token);
+        self.read.clear();
+        self.write.clear();
+        Ok(())
+    }
+
+    pub(crate) fn reregister(
+        &self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+        fd: RawFd,
+    ) -> io::Result<()> {
+        registry.selector().reregister(fd, token, interests)?;
+        *self.token.lock().unwrap() = Some(token);
+        Ok(())
+    }
+
+    pub(crate) fn deregister(&self, registry: &Registry, fd: RawFd) -> io::Result<()> {
+        registry.selector().deregister(fd)?;
+        *self.token.lock().unwrap() = None;
+        self.read.clear();
+        self.write.clear();
+        Ok(())
+    }
+
+    pub(crate) fn poll_read_ready(&self, cx: &mut task::Context<'_>) -> task::Poll<io::Result<()>> {
+        self.read.poll(cx).map(Ok)
+    }
+
+    pub(crate) fn poll_write_ready(
+        &self,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<io::Result<()>> {
+        self.write.poll(cx).map(Ok)
+    }
+
+    pub(crate) fn clear_read_ready(&self) {
+        self.read.clear();
+    }
+
+    pub(crate) fn clear_write_ready(&self) {
+        self.write.clear();
+    }
+
+    /// 如果`event`的token与最近一次register/reregister使用的token匹配,
+    /// 根据事件的可读/可写情况标记对应方向就绪并唤醒等待中的任务.<br>
+    /// If `event`'s token matches the one from the most recent
+    /// register/reregister call, mark the direction(s) it reports as ready
+    /// and wake any waiting task.
+    pub(crate) fn notify(&self, event: &Event) {
+        if *self.token.lock().unwrap() != Some(event.token()) {
+            return;
+        }
+
+        if event.is_readable() {
+            self.read.set();
+        }
+        if event.is_writable() {
+            self.write.set();
+        }
+    }
+}
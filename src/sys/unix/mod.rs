@@ -0,0 +1,5 @@
+mod io_source;
+mod waker;
+
+pub(crate) use io_source::IoSourceState;
+pub(crate) use waker::Waker;
@@ -0,0 +1,152 @@
+use std::io;
+use std::os::unix::io::RawFd;
+
+use crate::sys::Selector;
+use crate::{Interest, Token};
+
+/// 一个Waker拥有自己专属的eventfd,以自己的`token`注册到`selector`,与其它
+/// Waker完全独立.<br>
+/// A `Waker` owns a dedicated eventfd, registered with `selector` under its
+/// own `token`, entirely independent of any other `Waker`.
+///
+/// epoll(以及kqueue)原生支持在同一个selector上注册任意多个fd,各自携带独立
+/// 的token;让多个逻辑上独立的Waker共用一个fd并在用户态多路复用是不必要的
+/// 复杂度——一个共享的eventfd在epoll里只能携带一份token/data,selector自己并
+/// 不会把它翻译回多份携带不同token的事件,所以每个Waker直接复用selector已有
+/// 的[`register`]路径,和任何其它IO事件源没有区别,才是能真正工作的做法.<br>
+/// epoll (and kqueue) natively support registering any number of fds on the
+/// same selector, each carrying its own token, so multiplexing several
+/// logically independent wakers over one shared fd in userspace is
+/// unnecessary complexity — a shared eventfd can only carry a single
+/// token/data value in epoll, and the selector won't translate it back into
+/// several events with different tokens on its own. Each `Waker` simply
+/// reusing the selector's existing [`register`] path, no different from any
+/// other I/O event source, is what actually works.
+///
+/// [`register`]: Selector::register
+#[derive(Debug)]
+pub(crate) struct Waker {
+    fd: RawFd,
+}
+
+impl Waker {
+    pub(crate) fn new(selector: &Selector, token: Token) -> io::Result<Waker> {
+        // SAFETY: `libc::eventfd` is a simple syscall wrapper; we check its
+        // return value for the `-1` error sentinel below.
+        let fd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        if let Err(err) = selector.register(fd, token, Interest::READABLE) {
+            // SAFETY: `fd` was just created above and hasn't been handed
+            // out to anyone else yet.
+            unsafe { libc::close(fd) };
+            return Err(err);
+        }
+
+        Ok(Waker { fd })
+    }
+
+    /// 向eventfd写入数据使其变为可读,从而触发这个Waker自己的token对应的事件.<br>
+    /// Write to the eventfd to make it readable, triggering an event for
+    /// this waker's own token.
+    pub(crate) fn wake(&self) -> io::Result<()> {
+        let value: u64 = 1;
+        // SAFETY: writing a valid `u64` to a valid, open eventfd.
+        let res = unsafe {
+            libc::write(
+                self.fd,
+                &value as *const u64 as *const _,
+                std::mem::size_of::<u64>(),
+            )
+        };
+        if res == -1 {
+            let err = io::Error::last_os_error();
+            // EAGAIN means the eventfd counter is already saturated, i.e.
+            // it is already readable; that's fine, not an error.
+            if err.kind() != io::ErrorKind::WouldBlock {
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Waker {
+    fn drop(&mut self) {
+        // SAFETY: `fd` was obtained from `libc::eventfd` in `new` and is
+        // only ever closed here.
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::io::RawFd;
+
+    /// 直接操作eventfd验证`wake`所依赖的写入语义:写入会使其变为可读,并且
+    /// 在被读出之前重复写入不会报错(计数器被继续递增即可,不要求反映调用
+    /// 次数).<br>
+    /// Exercises the eventfd write semantics `wake` relies on directly:
+    /// writing makes it readable, and writing again before it's drained
+    /// doesn't error (the counter is simply incremented further; nothing
+    /// requires it to track the number of calls).
+    fn make_eventfd() -> RawFd {
+        let fd = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+        assert_ne!(fd, -1);
+        fd
+    }
+
+    fn write_one(fd: RawFd) {
+        let value: u64 = 1;
+        let res = unsafe {
+            libc::write(
+                fd,
+                &value as *const u64 as *const _,
+                std::mem::size_of::<u64>(),
+            )
+        };
+        assert_ne!(res, -1);
+    }
+
+    fn read_value(fd: RawFd) -> u64 {
+        let mut value: u64 = 0;
+        unsafe {
+            libc::read(
+                fd,
+                &mut value as *mut u64 as *mut _,
+                std::mem::size_of::<u64>(),
+            );
+        }
+        value
+    }
+
+    #[test]
+    fn writing_makes_the_eventfd_readable() {
+        let fd = make_eventfd();
+        write_one(fd);
+        assert_eq!(read_value(fd), 1);
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn writing_twice_before_a_read_is_not_an_error() {
+        let fd = make_eventfd();
+        write_one(fd);
+        write_one(fd);
+        assert_eq!(read_value(fd), 2);
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn reading_drains_the_counter() {
+        let fd = make_eventfd();
+        write_one(fd);
+        assert_eq!(read_value(fd), 1);
+        assert_eq!(read_value(fd), 0);
+        unsafe { libc::close(fd) };
+    }
+}
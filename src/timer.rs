@@ -0,0 +1,402 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::{event, Interest, Registry, Token, Waker};
+
+/// 时间轮的槽位数量,必须是2的幂.<br>
+/// Number of slots in the timing wheel. Must be a power of two.
+const NUM_SLOTS: usize = 256;
+
+/// 每个槽位代表的默认时长.<br>
+/// Default duration a single slot (tick) represents.
+const DEFAULT_TICK_DURATION: Duration = Duration::from_millis(100);
+
+/// 超时句柄,由[`Timer::set_timeout`]返回,传给[`Timer::cancel_timeout`]以取消.<br>
+/// An opaque handle to a scheduled timeout.
+///
+/// Returned by [`Timer::set_timeout`] and accepted by
+/// [`Timer::cancel_timeout`] to cancel the timeout before it fires.
+///
+/// [`Timer::set_timeout`]: Timer::set_timeout
+/// [`Timer::cancel_timeout`]: Timer::cancel_timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout {
+    id: u64,
+    slot: usize,
+}
+
+/// 时间轮中的一个条目,payload是用户数据,rotations是还需要经过的完整轮次.<br>
+/// One entry in the wheel: the user payload plus the number of full
+/// rotations still left before it actually expires.
+struct Entry<T> {
+    id: u64,
+    rotations: u64,
+    payload: T,
+}
+
+/// 散列时间轮本身,由[`Timer`]在内部用Mutex保护以支持跨线程`set_timeout`.<br>
+/// The hashed timing wheel itself. Wrapped in a `Mutex` by [`Timer`] so
+/// `set_timeout` can be called from any thread.
+struct Wheel<T> {
+    /// 每个槽位代表的时长
+    tick: Duration,
+    /// 固定大小的槽位数组,每个槽位是一个entry队列
+    slots: Vec<VecDeque<Entry<T>>>,
+    /// 当前指向的槽位
+    cursor: usize,
+    /// 时间轮已经前进到的最后一个tick边界(或创建)的时刻,用于计算到现在为止
+    /// 实际流逝了多少个tick.
+    /// 在构造时就初始化为当前时刻,而不是惰性地在第一次advance时才设置,否则
+    /// 第一轮到期的定时器会被多延迟一个完整的tick.
+    last_advance: Instant,
+}
+
+impl<T> Wheel<T> {
+    fn new(tick: Duration) -> Wheel<T> {
+        Wheel {
+            tick,
+            slots: (0..NUM_SLOTS).map(|_| VecDeque::new()).collect(),
+            cursor: 0,
+            last_advance: Instant::now(),
+        }
+    }
+
+    /// 插入一个延迟`delay`的定时器,返回其所在的槽位下标.<br>
+    /// 延迟不足一个tick的定时器仍然在下一个tick触发,避免零延迟的busy-loop.
+    fn insert(&mut self, id: u64, delay: Duration, payload: T) -> usize {
+        let ticks = (delay.as_nanos() / self.tick.as_nanos()).max(1) as u64;
+        let slot = (self.cursor as u64 + ticks) % NUM_SLOTS as u64;
+        // `ticks`个tick之后,指针恰好第`(ticks - 1) / NUM_SLOTS + 1`次扫到`slot`
+        // 这个槽位(当`ticks`是`NUM_SLOTS`的整数倍时,指针第一次回到`slot`正是本轮
+        // 到期的那一次,而不是还要再转一整圈)。用`(ticks - 1) / NUM_SLOTS`
+        // 计算还需要的*额外*整圈数,否则整数倍`NUM_SLOTS`的超时会被多等一整
+        // 圈才触发.<br>
+        // After `ticks` ticks the cursor sweeps past `slot` for the
+        // `(ticks - 1) / NUM_SLOTS + 1`-th time (when `ticks` is an exact multiple of
+        // `NUM_SLOTS`, the cursor's first return to `slot` *is* the due
+        // lap, not one lap later). `(ticks - 1) / NUM_SLOTS` gives the
+        // number of *extra* full rotations still needed; without the `- 1`,
+        // timeouts whose tick count is an exact multiple of `NUM_SLOTS`
+        // fire one full rotation late.
+        let rotations = (ticks - 1) / NUM_SLOTS as u64;
+        self.slots[slot as usize].push_back(Entry {
+            id,
+            rotations,
+            payload,
+        });
+        slot as usize
+    }
+
+    fn cancel(&mut self, timeout: Timeout) -> Option<T> {
+        let slot = &mut self.slots[timeout.slot];
+        let idx = slot.iter().position(|entry| entry.id == timeout.id)?;
+        slot.remove(idx).map(|entry| entry.payload)
+    }
+
+    /// 到下一个*真正*到期的条目的时长,扣除自上次advance以来已经流逝的时间.
+    /// 一个槽位非空不代表它里面的条目本轮就到期:还要看该槽位中剩余rotations
+    /// 最少的条目,它还需要经过`rotations`次完整的轮转才会真正到期.忽略这一点
+    /// 会导致多轮定时器在被扫过但尚未到期的那次返回`Duration::ZERO`,造成
+    /// busy-loop.<br>
+    /// Duration until the next entry that will *actually* expire, net of
+    /// time already elapsed since the wheel was last advanced. A non-empty
+    /// slot doesn't mean an entry in it is due this lap: the entry with the
+    /// fewest remaining `rotations` in that slot still needs that many full
+    /// wheel rotations before it truly expires. Ignoring that makes a
+    /// multi-rotation timeout return `Duration::ZERO` every time its slot is
+    /// swept but not yet due, causing a busy-loop.
+    ///
+    /// `steps`取值范围是`1..=NUM_SLOTS`而不是`0..NUM_SLOTS`:`advance()`是先把
+    /// `cursor`前进一格,再检查新位置的槽位,所以当前`cursor`所在的槽位要等
+    /// *一整圈*(`NUM_SLOTS`个tick)之后才会被再次扫到,而不是"0个tick之后".
+    /// 把当前槽位当成`steps == 0`,会让延迟恰好是轮子周期整数倍的定时器在
+    /// 刚插入后就让本方法返回`Duration::ZERO`,造成busy-loop——这正是这两次
+    /// "修复"提交本该消除的那个bug.<br>
+    /// `steps` ranges over `1..=NUM_SLOTS`, not `0..NUM_SLOTS`: `advance()`
+    /// increments `cursor` first and only then inspects the new slot, so the
+    /// slot the cursor currently sits on isn't swept again until a *full
+    /// rotation* (`NUM_SLOTS` ticks) later, not "0 ticks later". Treating the
+    /// current slot as `steps == 0` makes a timeout whose delay is an exact
+    /// multiple of the wheel period return `Duration::ZERO` from this method
+    /// right after being inserted, causing a busy-loop — exactly the bug
+    /// these two "fix" commits were supposed to eliminate.
+    fn poll_timeout(&self) -> Option<Duration> {
+        let elapsed = self.last_advance.elapsed();
+        (1..=NUM_SLOTS)
+            .filter_map(|steps| {
+                let slot = &self.slots[(self.cursor + steps) % NUM_SLOTS];
+                let min_rotations = slot.iter().map(|entry| entry.rotations).min()?;
+                Some(steps as u64 + min_rotations * NUM_SLOTS as u64)
+            })
+            .min()
+            .map(|ticks| (self.tick * ticks as u32).saturating_sub(elapsed))
+    }
+
+    /// 按实际流逝的时间前进时间轮,扫过的槽位中到期(rotations为0)的条目被取出返回.<br>
+    /// Advance the wheel by however many ticks have actually elapsed,
+    /// collecting entries in swept slots whose rotation count reaches zero.
+    fn advance(&mut self) -> Vec<T> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_advance);
+        let ticks = (elapsed.as_nanos() / self.tick.as_nanos()) as u64;
+        // 只前进整数个tick,不足一个tick的剩余时间留到下一次advance.
+        // Only whole ticks are consumed; the sub-tick remainder carries over
+        // to the next advance.
+        self.last_advance += self.tick * ticks as u32;
+
+        let mut expired = Vec::new();
+        for _ in 0..ticks {
+            self.cursor = (self.cursor + 1) % NUM_SLOTS;
+            let mut remaining = VecDeque::new();
+            for mut entry in self.slots[self.cursor].drain(..) {
+                if entry.rotations == 0 {
+                    expired.push(entry.payload);
+                } else {
+                    entry.rotations -= 1;
+                    remaining.push_back(entry);
+                }
+            }
+            self.slots[self.cursor] = remaining;
+        }
+        expired
+    }
+}
+
+/// 基于散列时间轮的定时器,可注册到[`Poll`]/[`Registry`]中驱动超时事件.<br>
+/// A hashed timing-wheel timer that can be registered with [`Poll`] /
+/// [`Registry`] to drive timeouts alongside I/O events.
+///
+/// `Timer<T>`实现了[`event::Source`],注册后内部会持有一个[`Waker`],使得从另一个
+/// 线程调用[`set_timeout`]可以打断正阻塞在`poll`中的事件循环.<br>
+/// `Timer<T>` implements [`event::Source`]. Once registered it holds an
+/// internal [`Waker`] so calling [`set_timeout`] from another thread can
+/// interrupt an event loop blocked in `poll`.
+///
+/// # 注意, Notes
+///
+/// 事件循环应当用[`poll_timeout`]的返回值作为`Poll::poll`的超时时长,并且不论
+/// `poll`是因为真实事件还是超时返回,都要调用[`expired`]来收割到期的定时器。<br>
+/// The event loop should pass the result of [`poll_timeout`] as the timeout
+/// to `Poll::poll`, and call [`expired`] after every `poll` call, whether it
+/// returned because of a real event or because the timeout elapsed.
+///
+/// [`Poll`]: crate::Poll
+/// [`Waker`]: crate::Waker
+/// [`set_timeout`]: Timer::set_timeout
+/// [`poll_timeout`]: Timer::poll_timeout
+/// [`expired`]: Timer::expired
+///
+/// # 例子, Examples
+///
+/// ```
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use std::time::Duration;
+///
+/// use mio::{Events, Interest, Poll, Token};
+/// use mio::Timer;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(2);
+/// let mut timer = Timer::<&'static str>::new();
+///
+/// poll.registry()
+///     .register(&mut timer, Token(0), Interest::READABLE)?;
+///
+/// timer.set_timeout(Duration::from_millis(10), "hello");
+///
+/// // `set_timeout` wakes the registered `Poll`, so the first `poll` can
+/// // return before the timeout is due; poll until it has expired.
+/// let mut expired = Vec::new();
+/// while expired.is_empty() {
+///     poll.poll(&mut events, timer.poll_timeout())?;
+///     expired = timer.expired();
+/// }
+/// assert_eq!(expired, vec!["hello"]);
+/// #     Ok(())
+/// # }
+/// ```
+pub struct Timer<T> {
+    wheel: Mutex<Wheel<T>>,
+    next_id: AtomicU64,
+    /// 用于从其它线程打断阻塞中的Poll,注册之后才会存在.<br>
+    /// Used to interrupt a blocked `Poll` from another thread. Only present
+    /// once the `Timer` has been registered.
+    waker: Mutex<Option<Waker>>,
+}
+
+impl<T> Timer<T> {
+    /// 使用默认的tick时长(100ms)创建一个`Timer`.<br>
+    /// Create a new `Timer` using the default tick duration (100ms).
+    pub fn new() -> Timer<T> {
+        Timer::with_tick_duration(DEFAULT_TICK_DURATION)
+    }
+
+    /// 使用自定义的tick时长创建一个`Timer`.<br>
+    /// Create a new `Timer` using a custom tick duration.
+    pub fn with_tick_duration(tick: Duration) -> Timer<T> {
+        Timer {
+            wheel: Mutex::new(Wheel::new(tick)),
+            next_id: AtomicU64::new(0),
+            waker: Mutex::new(None),
+        }
+    }
+
+    /// 设置一个在`delay`之后触发的定时器,携带`payload`.<br>
+    /// Schedule a timeout that fires after `delay`, carrying `payload`.
+    ///
+    /// 可以从任意线程调用,包括当前事件循环正阻塞在`Poll::poll`中的线程。<br>
+    /// May be called from any thread, including while the event loop is
+    /// blocked in `Poll::poll` on another thread.
+    pub fn set_timeout(&self, delay: Duration, payload: T) -> Timeout {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let slot = self.wheel.lock().unwrap().insert(id, delay, payload);
+
+        // 如果事件循环正阻塞在别的线程上,唤醒它以便重新计算下一次超时。
+        // If the event loop is blocked on another thread, wake it so it
+        // recomputes the next timeout.
+        if let Some(waker) = self.waker.lock().unwrap().as_ref() {
+            let _ = waker.wake();
+        }
+
+        Timeout { id, slot }
+    }
+
+    /// 取消一个尚未触发的定时器,返回其payload(如果还存在的话)。<br>
+    /// Cancel a timeout that has not yet fired, returning its payload if it
+    /// was still pending.
+    pub fn cancel_timeout(&self, timeout: &Timeout) -> Option<T> {
+        self.wheel.lock().unwrap().cancel(*timeout)
+    }
+
+    /// 距离下一个定时器到期的时长,可直接传给`Poll::poll`。<br>
+    /// Duration until the next timeout expires, suitable for passing
+    /// directly to `Poll::poll`.
+    pub fn poll_timeout(&self) -> Option<Duration> {
+        self.wheel.lock().unwrap().poll_timeout()
+    }
+
+    /// 根据实际流逝的时间推进时间轮,返回所有到期的payload。<br>
+    /// Advance the wheel by however much time has actually elapsed,
+    /// returning the payloads of all timeouts that have expired.
+    ///
+    /// 应当在每次`Poll::poll`返回之后调用,无论返回原因是真实事件还是超时。<br>
+    /// Should be called after every `Poll::poll` return, regardless of
+    /// whether it returned due to a real event or the timeout elapsing.
+    pub fn expired(&self) -> Vec<T> {
+        self.wheel.lock().unwrap().advance()
+    }
+}
+
+impl<T> Default for Timer<T> {
+    fn default() -> Timer<T> {
+        Timer::new()
+    }
+}
+
+impl<T> event::Source for Timer<T> {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        _interests: Interest,
+    ) -> io::Result<()> {
+        let waker = Waker::new(registry, token)?;
+        *self.waker.lock().unwrap() = Some(waker);
+        Ok(())
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.register(registry, token, interests)
+    }
+
+    fn deregister(&mut self, _registry: &Registry) -> io::Result<()> {
+        *self.waker.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+impl<T> fmt::Debug for Timer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timer").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 回归测试:延迟恰好是`NUM_SLOTS`整数倍的定时器,之前会被多等一整圈才
+    /// 触发(例如256个tick的延迟在第512个tick才触发).<br>
+    /// Regression test: a timeout whose delay is an exact multiple of
+    /// `NUM_SLOTS` used to fire one full rotation late (e.g. a 256-tick
+    /// delay firing at tick 512 instead of tick 256).
+    #[test]
+    fn insert_exact_multiple_of_wheel_period_fires_within_one_tick() {
+        let tick = Duration::from_millis(1);
+        let mut wheel: Wheel<&'static str> = Wheel::new(tick);
+
+        wheel.insert(0, tick * NUM_SLOTS as u32, "exact-multiple");
+
+        std::thread::sleep(tick * (NUM_SLOTS as u32 + 1));
+        assert_eq!(wheel.advance(), vec!["exact-multiple"]);
+    }
+
+    /// 回归测试:延迟恰好是一个轮子周期的定时器,插入后立刻调用
+    /// `poll_timeout()`应当返回接近整个周期的时长,而不是`Duration::ZERO`
+    /// (否则事件循环会在真正到期前一直busy-loop).<br>
+    /// Regression test: a timeout whose delay is exactly one wheel period
+    /// must have `poll_timeout()` report close to the full period right
+    /// after insertion, not `Duration::ZERO` (otherwise the event loop
+    /// busy-loops until the timeout is actually due).
+    #[test]
+    fn poll_timeout_for_exact_multiple_of_wheel_period_is_not_zero() {
+        let tick = Duration::from_millis(1);
+        let mut wheel: Wheel<&'static str> = Wheel::new(tick);
+
+        wheel.insert(0, tick * NUM_SLOTS as u32, "exact-multiple");
+
+        let timeout = wheel.poll_timeout().expect("a timeout is pending");
+        assert!(
+            timeout >= tick * (NUM_SLOTS as u32 - 1),
+            "expected close to a full wheel period, got {timeout:?}"
+        );
+    }
+
+    /// 延迟是`NUM_SLOTS`整数倍的两倍(第二次整圈),同样应当按时触发.<br>
+    /// A delay that is twice the wheel period (due on the second lap) should
+    /// likewise fire on time, not a full rotation late.
+    #[test]
+    fn insert_at_second_wheel_period_fires_within_one_tick() {
+        let tick = Duration::from_millis(1);
+        let mut wheel: Wheel<&'static str> = Wheel::new(tick);
+
+        wheel.insert(0, tick * (2 * NUM_SLOTS as u32), "second-lap");
+
+        std::thread::sleep(tick * (2 * NUM_SLOTS as u32 + 1));
+        assert_eq!(wheel.advance(), vec!["second-lap"]);
+    }
+
+    /// 非整数倍的延迟此前也能正确触发,确保修复没有破坏这一路径.<br>
+    /// Delays that aren't an exact multiple fired correctly even before the
+    /// fix; confirm it still works.
+    #[test]
+    fn insert_non_multiple_of_wheel_period_fires_within_one_tick() {
+        let tick = Duration::from_millis(1);
+        let mut wheel: Wheel<&'static str> = Wheel::new(tick);
+
+        wheel.insert(0, tick * 300, "non-multiple");
+
+        std::thread::sleep(tick * 301);
+        assert_eq!(wheel.advance(), vec!["non-multiple"]);
+    }
+}
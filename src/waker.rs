@@ -18,24 +18,48 @@ use std::io;
 /// [`wake`]: struct.Waker.html#method.wake
 ///
 /// # 注意, Notes
-/// 
+///
 /// 仅在Waker确定存活的情况下才能被投递Waker事件.<br>
 /// `Waker` events are only guaranteed to be delivered while the `Waker` value
 /// is alive.
 ///
-/// 每个Poll实例只能激活一个Waker.如果需要多线程访问可以使用Arc<Waker>.
-/// 当一个Poll实例注册了多个Waker时会发生什么是不确定的.<br>
-/// Only a single `Waker` can be active per [`Poll`], if multiple threads need
-/// access to the `Waker` it can be shared via for example an `Arc`. What
-/// happens if multiple `Waker`s are registered with the same `Poll` is
-/// unspecified.
+/// 每个Poll实例可以注册多个Waker,只要它们使用各自独立的Token.每个Waker只负责
+/// 投递携带自己Token的事件,两个Waker之间互不影响.如果需要多线程访问同一个Waker
+/// 可以使用Arc<Waker>.<br>
+/// Multiple `Waker`s can be registered with the same [`Poll`], as long as
+/// each uses its own `Token`. Each `Waker` only ever delivers events carrying
+/// its own `Token`, so distinct wakers (for example a "shutdown" waker and a
+/// "new work" waker on the same loop) don't interfere with each other. If
+/// multiple threads need access to the same `Waker` it can be shared via for
+/// example an `Arc`.
+///
+/// 并发约定: 如果两个Waker在同一次`poll`之间各自`wake()`过至少一次,那么下一次
+/// `poll`返回时,两个Token对应的事件都保证被投递(可能合并成同一批事件,但不会
+/// 丢失);对同一个Waker的多次`wake()`调用只保证至少投递一次事件,不保证投递
+/// 次数与调用次数相同.<br>
+/// Concurrency contract: if two wakers each call `wake()` at least once
+/// between two calls to `poll`, the next `poll` is guaranteed to deliver an
+/// event for both tokens (possibly in the same batch, but never dropped).
+/// Multiple `wake()` calls on the *same* waker before the next `poll` are
+/// only guaranteed to deliver at least one event for its token, not one
+/// event per call.
 ///
 /// # 实现说明, Implementation notes
 ///
-/// 在支持kqueue的平台,使用`EVFILT_USER`事件过滤器.<br>
-/// On platforms that support kqueue this will use the `EVFILT_USER` event
-/// filter, see [implementation notes of `Poll`] to see what platforms support
-/// kqueue. On Linux it uses [eventfd].
+/// 所有Unix平台都使用下面描述的eventfd实现;没有基于kqueue `EVFILT_USER`的
+/// 实现.<br>
+/// Every Unix platform uses the eventfd implementation described below;
+/// there is no kqueue `EVFILT_USER` based implementation (see
+/// [implementation notes of `Poll`] for the platforms that use kqueue).
+///
+/// 在Linux上,每个Waker拥有一个专属的eventfd,以自己的Token注册到selector——
+/// epoll原生支持在同一个实例上注册任意多个fd,各自携带独立的token,所以不需要
+/// 在用户态把多个逻辑Waker多路复用到一个共享fd上.<br>
+/// On Linux each `Waker` owns a dedicated eventfd, registered with the
+/// selector under its own `Token`. epoll natively supports registering any
+/// number of fds on one instance, each carrying its own token, so there is
+/// no need to multiplex several logical wakers over a shared fd in
+/// userspace.
 ///
 /// [implementation notes of `Poll`]: struct.Poll.html#implementation-notes
 /// [eventfd]: https://man7.org/linux/man-pages/man2/eventfd.2.html
@@ -85,6 +109,33 @@ use std::io;
 /// #     Ok(())
 /// # }
 /// ```
+///
+/// 在同一个[`Poll`]上注册多个各自使用不同Token的Waker.<br>
+/// Register several `Waker`s with distinct tokens on the same [`Poll`].
+///
+#[cfg_attr(feature = "os-poll", doc = "```")]
+#[cfg_attr(not(feature = "os-poll"), doc = "```ignore")]
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use mio::{Events, Token, Poll, Waker};
+///
+/// const SHUTDOWN_TOKEN: Token = Token(10);
+/// const NEW_WORK_TOKEN: Token = Token(11);
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(4);
+///
+/// // Two independent wakers, each responsible for its own wake reason.
+/// let shutdown_waker = Waker::new(poll.registry(), SHUTDOWN_TOKEN)?;
+/// let new_work_waker = Waker::new(poll.registry(), NEW_WORK_TOKEN)?;
+///
+/// new_work_waker.wake()?;
+///
+/// poll.poll(&mut events, None)?;
+/// assert!(events.iter().any(|event| event.token() == NEW_WORK_TOKEN));
+/// assert!(!events.iter().any(|event| event.token() == SHUTDOWN_TOKEN));
+/// #     Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Waker {
     inner: sys::Waker,
@@ -95,13 +146,19 @@ impl Waker {
     /// Create a new `Waker`.
     ///
     /// 关联关系
-    /// Waker -> sys::Waker -> sys::WakerInternal -> std::fs::File -> eventfd
+    /// Waker -> sys::Waker -> 专属的eventfd,以`token`注册到`registry`的selector
     ///
     /// registry提供了事件监听对象,如epoll
-    /// token唤醒事件句柄eventfd注册到epoll时设置的token
+    /// token唤醒事件句柄,多个Waker可以共用同一个registry,只要各自的token不同
     pub fn new(registry: &Registry, token: Token) -> io::Result<Waker> {
-        #[cfg(debug_assertions)]
-        registry.register_waker();
+        // 不再有"每个Poll只能有一个Waker"的限制:`sys::Waker`给每个Waker分配
+        // 专属的eventfd并以`token`注册到selector,多个Waker天然互不干扰,不需要
+        // 在用户态做任何单例校验或事件多路复用.
+        // There is no longer a "single Waker per Poll" restriction:
+        // `sys::Waker` gives each Waker its own eventfd, registered with the
+        // selector under `token`. Distinct wakers naturally don't interfere
+        // with each other, with no singleton check or userspace event
+        // multiplexing required.
         sys::Waker::new(registry.selector(), token).map(|inner| Waker { inner })
     }
 